@@ -1,4 +1,13 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
 use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::crypto;
+use crate::png::Png;
+use crate::Error;
 
 #[derive(Debug)]
 pub enum PngMeArgs {
@@ -18,12 +27,14 @@ pub struct EncodeArgs {
     file: PathBuf,
     chunk_type: String,
     payload: String,
+    public_key: Option<PathBuf>,
 }
 
 #[derive(Debug)]
 pub struct DecodeArgs {
     file: PathBuf,
     chunk_type: String,
+    private_key: Option<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -32,9 +43,63 @@ pub struct RemoveArgs {
     chunk_type: String
 }
 
+impl EncodeArgs {
+    pub fn execute(&self) -> crate::Result<()> {
+        let mut png = Png::from_reader(&mut BufReader::new(File::open(&self.file)?))?;
+
+        let (chunk_type_str, data) = match &self.public_key {
+            Some(key_path) => {
+                let recipient = crypto::load_public_key(key_path)?;
+                let encrypted = crypto::encrypt(&recipient, self.payload.as_bytes())?;
+                (crypto::private_chunk_type(&self.chunk_type), encrypted)
+            }
+            None => (self.chunk_type.clone(), self.payload.as_bytes().to_vec()),
+        };
+
+        let chunk_type = ChunkType::from_str(&chunk_type_str)?;
+        png.append_chunk(Chunk::new(chunk_type, data));
+
+        png.write_to(&mut BufWriter::new(File::create(&self.file)?))?;
+        Ok(())
+    }
+}
+
+impl DecodeArgs {
+    pub fn execute(&self) -> crate::Result<String> {
+        let png = Png::from_reader(&mut BufReader::new(File::open(&self.file)?))?;
+
+        let chunk_type = match &self.private_key {
+            Some(_) => crypto::private_chunk_type(&self.chunk_type),
+            None => self.chunk_type.clone(),
+        };
+
+        let chunk = png.chunk_by_type(&chunk_type)
+            .ok_or_else(|| Box::new(io::Error::new(io::ErrorKind::NotFound, "chunk type not present")) as Error)?;
+
+        match &self.private_key {
+            Some(key_path) => {
+                let recipient_private = crypto::load_private_key(key_path)?;
+                let plaintext = crypto::decrypt(&recipient_private, chunk.data())?;
+                String::from_utf8(plaintext).map_err(|e| Box::new(e) as Error)
+            }
+            None => Ok(chunk.to_string()),
+        }
+    }
+}
+
+impl RemoveArgs {
+    pub fn execute(&self) -> crate::Result<Chunk> {
+        let mut png = Png::from_reader(&mut BufReader::new(File::open(&self.file)?))?;
+
+        let chunk = png.remove_first_chunk(&self.chunk_type)?;
+        png.write_to(&mut BufWriter::new(File::create(&self.file)?))?;
+
+        Ok(chunk)
+    }
+}
 
 pub fn generate_args(command: &str, filepath: &str, chunk_type: Option<&str>,
-    payload: Option<&str>) -> Result<PngMeArgs, ArgErr> {
+    payload: Option<&str>, key_path: Option<&str>) -> Result<PngMeArgs, ArgErr> {
     // Check for valid filepath since that's common to everything.
     match command {
         "encode" => {
@@ -45,7 +110,8 @@ pub fn generate_args(command: &str, filepath: &str, chunk_type: Option<&str>,
             } else {
                 Ok(PngMeArgs::Encode(EncodeArgs { file: PathBuf::from(filepath),
                                                   chunk_type: String::from(chunk_type.unwrap()),
-                                                  payload: String::from(payload.unwrap()) }))
+                                                  payload: String::from(payload.unwrap()),
+                                                  public_key: key_path.map(PathBuf::from) }))
             }
         },
         "decode" => {
@@ -53,7 +119,8 @@ pub fn generate_args(command: &str, filepath: &str, chunk_type: Option<&str>,
                 Err(ArgErr::MissingArgs(String::from("chunk type")))
             } else {
                 Ok(PngMeArgs::Decode (DecodeArgs { file: PathBuf::from(filepath),
-                                                chunk_type: String::from(chunk_type.unwrap()) }))
+                                                chunk_type: String::from(chunk_type.unwrap()),
+                                                private_key: key_path.map(PathBuf::from) }))
             }
         }
         "remove" => {
@@ -71,53 +138,112 @@ pub fn generate_args(command: &str, filepath: &str, chunk_type: Option<&str>,
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+
     #[test]
     pub fn test_encode_valid() {
-        let result = generate_args("encode", "./foo.txt", Some("ruSt"), Some("Deadbeef"));
+        let result = generate_args("encode", "./foo.txt", Some("ruSt"), Some("Deadbeef"), None);
         assert!(result.is_ok());
         assert!(matches!(result.unwrap(), PngMeArgs::Encode(_)));
     }
 
     #[test]
     pub fn test_encode_missing_args_chunk_type() {
-        let result = generate_args("encode", "./foo.txt", None, Some("Deadbeef"));
+        let result = generate_args("encode", "./foo.txt", None, Some("Deadbeef"), None);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ArgErr::MissingArgs(String::from("chunk type")));
     }
 
     #[test]
     pub fn test_encode_missing_args_payload() {
-        let result = generate_args("encode", "./foo.txt", Some("ruSt"), None);
+        let result = generate_args("encode", "./foo.txt", Some("ruSt"), None, None);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ArgErr::MissingArgs(String::from("payload")));
     }
 
     #[test]
     pub fn test_decode_valid() {
-        let result = generate_args("decode", "./foo.txt", Some("ruSt"), None);
+        let result = generate_args("decode", "./foo.txt", Some("ruSt"), None, None);
         assert!(result.is_ok());
         assert!(matches!(result.unwrap(), PngMeArgs::Decode(_)));
     }
 
     #[test]
     pub fn test_decode_missing_args_chunk_type() {
-        let result = generate_args("decode", "./foo.txt", None, None);
+        let result = generate_args("decode", "./foo.txt", None, None, None);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ArgErr::MissingArgs(String::from("chunk type")));
     }
 
     #[test]
     pub fn test_remove_valid() {
-        let result = generate_args("remove", "./foo.txt", Some("ruSt"), None);
+        let result = generate_args("remove", "./foo.txt", Some("ruSt"), None, None);
         assert!(result.is_ok());
         assert!(matches!(result.unwrap(), PngMeArgs::Remove(_)));
     }
 
     #[test]
     pub fn test_remove_missing_args_chunk_type() {
-        let result = generate_args("remove", "./foo.txt", None, None);
+        let result = generate_args("remove", "./foo.txt", None, None, None);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ArgErr::MissingArgs(String::from("chunk type")));
     }
+
+    #[test]
+    pub fn test_encode_valid_with_public_key() {
+        let result = generate_args("encode", "./foo.txt", Some("ruSt"), Some("Deadbeef"), Some("./pub.pem"));
+        assert!(result.is_ok());
+        assert!(matches!(result.unwrap(), PngMeArgs::Encode(_)));
+    }
+
+    #[test]
+    pub fn test_decode_valid_with_private_key() {
+        let result = generate_args("decode", "./foo.txt", Some("ruSt"), None, Some("./priv.pem"));
+        assert!(result.is_ok());
+        assert!(matches!(result.unwrap(), PngMeArgs::Decode(_)));
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip_via_execute() {
+        use p256::elliptic_curve::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+        use p256::SecretKey;
+        use std::io::Write as _;
+
+        let secret = SecretKey::random(&mut aes_gcm::aead::OsRng);
+
+        let mut pub_path = std::env::temp_dir();
+        pub_path.push(format!("pngme-test-pub-{:p}.pem", &secret));
+        let mut priv_path = std::env::temp_dir();
+        priv_path.push(format!("pngme-test-priv-{:p}.pem", &secret));
+        let mut png_path = std::env::temp_dir();
+        png_path.push(format!("pngme-test-{:p}.png", &secret));
+
+        fs::write(&pub_path, secret.public_key().to_public_key_pem(LineEnding::LF).unwrap()).unwrap();
+        fs::write(&priv_path, secret.to_pkcs8_pem(LineEnding::LF).unwrap().as_bytes()).unwrap();
+
+        let mut file = fs::File::create(&png_path).unwrap();
+        file.write_all(&Png::from_chunks(Vec::new()).as_bytes()).unwrap();
+        drop(file);
+
+        let encode = EncodeArgs {
+            file: png_path.clone(),
+            chunk_type: String::from("RUSt"),
+            payload: String::from("hidden message"),
+            public_key: Some(pub_path.clone()),
+        };
+        encode.execute().unwrap();
+
+        let decode = DecodeArgs {
+            file: png_path.clone(),
+            chunk_type: String::from("RUSt"),
+            private_key: Some(priv_path.clone()),
+        };
+        let plaintext = decode.execute().unwrap();
+        assert_eq!(plaintext, "hidden message");
+
+        fs::remove_file(&pub_path).unwrap();
+        fs::remove_file(&priv_path).unwrap();
+        fs::remove_file(&png_path).unwrap();
+    }
 }
 
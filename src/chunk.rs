@@ -1,16 +1,140 @@
 
 use crate::{chunk_type::ChunkType, Error, Result};
 use core::str;
-use std::io;
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
 use std::str::FromStr;
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
 use crc32fast::Hasher;
+use rmpv::Value;
+use serde::{Deserialize, Serialize};
+
+const ARMOR_HEADER: &str = "-----BEGIN PNGME CHUNK-----";
+const ARMOR_FOOTER: &str = "-----END PNGME CHUNK-----";
+const ARMOR_LINE_WIDTH: usize = 64;
+
+// RLP-style recursive length-prefix framing (see Chunk::new_list / Chunk::items) so several
+// independent byte strings can be packed into, and unpacked from, a single chunk's data.
+
+fn rlp_encode_item(item: &[u8]) -> Vec<u8> {
+    if item.len() == 1 && item[0] < 0x80 {
+        return vec![item[0]];
+    }
+
+    if item.len() <= 55 {
+        let mut encoded = vec![0x80 + item.len() as u8];
+        encoded.extend_from_slice(item);
+        return encoded;
+    }
+
+    let len_be = item.len().to_be_bytes();
+    let len_bytes = strip_leading_zeros(&len_be);
+    let mut encoded = vec![0xb7 + len_bytes.len() as u8];
+    encoded.extend_from_slice(len_bytes);
+    encoded.extend_from_slice(item);
+    encoded
+}
+
+fn rlp_encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload: Vec<u8> = items.iter().flat_map(|item| rlp_encode_item(item)).collect();
+
+    if payload.len() <= 55 {
+        let mut encoded = vec![0xc0 + payload.len() as u8];
+        encoded.extend_from_slice(&payload);
+        return encoded;
+    }
+
+    let len_be = payload.len().to_be_bytes();
+    let len_bytes = strip_leading_zeros(&len_be);
+    let mut encoded = vec![0xf7 + len_bytes.len() as u8];
+    encoded.extend_from_slice(len_bytes);
+    encoded.extend_from_slice(&payload);
+    encoded
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let first_nonzero = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1);
+    &bytes[first_nonzero..]
+}
+
+fn rlp_read_length(bytes: &[u8], len_of_len: usize) -> Result<usize> {
+    if len_of_len == 0 || len_of_len > 8 || bytes.len() < len_of_len {
+        return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "truncated RLP length")));
+    }
+    if bytes[0] == 0 {
+        return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "non-canonical RLP length")));
+    }
+
+    let mut padded = [0u8; 8];
+    padded[8 - len_of_len..].copy_from_slice(&bytes[..len_of_len]);
+    Ok(u64::from_be_bytes(padded) as usize)
+}
+
+fn rlp_read_item(data: &[u8]) -> Result<(Vec<u8>, usize)> {
+    if data.is_empty() {
+        return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "truncated RLP item")));
+    }
+
+    let prefix = data[0];
+    if prefix < 0x80 {
+        Ok((vec![prefix], 1))
+    } else if prefix <= 0xb7 {
+        let len = (prefix - 0x80) as usize;
+        if data.len() < 1 + len {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "truncated RLP item")));
+        }
+        Ok((data[1..1 + len].to_vec(), 1 + len))
+    } else if prefix <= 0xbf {
+        let len_of_len = (prefix - 0xb7) as usize;
+        let length = rlp_read_length(&data[1..], len_of_len)?;
+        let start = 1 + len_of_len;
+        if data.len() < start + length {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "truncated RLP item")));
+        }
+        Ok((data[start..start + length].to_vec(), start + length))
+    } else {
+        Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "expected RLP byte string, found list")))
+    }
+}
+
+fn rlp_read_list_payload(data: &[u8]) -> Result<&[u8]> {
+    if data.is_empty() {
+        return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "truncated RLP list")));
+    }
+
+    let prefix = data[0];
+    if prefix < 0xc0 {
+        Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "expected RLP list, found byte string")))
+    } else if prefix <= 0xf7 {
+        let len = (prefix - 0xc0) as usize;
+        if data.len() < 1 + len {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "truncated RLP list")));
+        }
+        Ok(&data[1..1 + len])
+    } else {
+        let len_of_len = (prefix - 0xf7) as usize;
+        let length = rlp_read_length(&data[1..], len_of_len)?;
+        let start = 1 + len_of_len;
+        if data.len() < start + length {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "truncated RLP list")));
+        }
+        Ok(&data[start..start + length])
+    }
+}
 
 pub struct Chunk {
     chunk_type: ChunkType,
     data: Vec<u8>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct StructuredPayload {
+    header: HashMap<String, Value>,
+    body: Vec<u8>,
+}
+
 impl Chunk {
     pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Chunk {
         Chunk {chunk_type: chunk_type, data: data}
@@ -32,18 +156,35 @@ impl Chunk {
         return hasher.finalize()
     }
 
-    fn chunk_type(&self) -> &ChunkType {
+    pub fn chunk_type(&self) -> &ChunkType {
         &self.chunk_type
     }
 
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
     fn data_as_string(&self) -> Result<String> {
         if self.chunk_type.is_valid() {
-            Ok(String::from_utf8(self.data.as_slice().to_vec()).unwrap())
+            String::from_utf8(self.data.as_slice().to_vec()).map_err(|e| Box::new(e) as Error)
         } else {
             Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "invalid chunk")))
         }
     }
 
+    /// Builds a chunk whose data is a MessagePack-encoded `{header, body}` record, so
+    /// callers can attach typed metadata (author, timestamp, ...) alongside the hidden bytes.
+    pub fn new_structured(chunk_type: ChunkType, header: HashMap<String, Value>, body: Vec<u8>) -> Result<Chunk> {
+        let data = rmp_serde::to_vec(&StructuredPayload { header, body })?;
+        Ok(Chunk::new(chunk_type, data))
+    }
+
+    /// Decodes a chunk produced by [`Chunk::new_structured`] back into its header and body.
+    pub fn structured_payload(&self) -> Result<(HashMap<String, Value>, Vec<u8>)> {
+        let payload: StructuredPayload = rmp_serde::from_slice(self.data.as_slice())?;
+        Ok((payload.header, payload.body))
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         let chunk_data: Vec<u8> = (self.data.len() as u32)
         .to_be_bytes()
@@ -56,8 +197,109 @@ impl Chunk {
 
         return chunk_data;
     }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_all(&(self.data.len() as u32).to_be_bytes())?;
+        w.write_all(&self.chunk_type.bytes())?;
+        w.write_all(&self.data)?;
+        w.write_all(&self.crc().to_be_bytes())?;
+        Ok(())
+    }
+
+    /// Base64-encodes `as_bytes()` inside a PEM-style armor block.
+    pub fn to_armored_string(&self) -> String {
+        let encoded = BASE64.encode(self.as_bytes());
+
+        let mut armored = String::from(ARMOR_HEADER);
+        armored.push('\n');
+        for line in encoded.as_bytes().chunks(ARMOR_LINE_WIDTH) {
+            armored.push_str(str::from_utf8(line).unwrap());
+            armored.push('\n');
+        }
+        armored.push_str(ARMOR_FOOTER);
+
+        armored
+    }
+
+    /// Parses the armor block produced by `to_armored_string` back into a `Chunk`.
+    pub fn from_armored_str(armored: &str) -> Result<Chunk> {
+        let stripped = armored.replace(ARMOR_HEADER, "").replace(ARMOR_FOOTER, "");
+        let body: String = stripped.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let bytes = BASE64.decode(body).map_err(|e| Box::new(e) as Error)?;
+        Chunk::try_from(bytes.as_slice())
+    }
+
+    /// Packs multiple byte strings into one chunk's data using RLP-style length-prefix framing.
+    pub fn new_list(chunk_type: ChunkType, items: Vec<Vec<u8>>) -> Chunk {
+        Chunk::new(chunk_type, rlp_encode_list(&items))
+    }
+
+    /// Unpacks the byte strings packed by `new_list`.
+    pub fn items(&self) -> Result<Vec<Vec<u8>>> {
+        let mut remainder = rlp_read_list_payload(&self.data)?;
+        let mut items = Vec::new();
+        while !remainder.is_empty() {
+            let (item, consumed) = rlp_read_item(remainder)?;
+            items.push(item);
+            remainder = &remainder[consumed..];
+        }
+
+        Ok(items)
+    }
+
+    /// Reads one chunk off `r` without buffering the whole file. Returns `Ok(None)` at a
+    /// clean EOF before any chunk starts.
+    pub fn from_reader<R: Read>(r: &mut R) -> Result<Option<Chunk>> {
+        let mut length_bytes = [0u8; 4];
+        if !read_exact_or_eof(r, &mut length_bytes)? {
+            return Ok(None);
+        }
+        let length = u32::from_be_bytes(length_bytes) as usize;
+
+        let mut type_bytes = [0u8; 4];
+        r.read_exact(&mut type_bytes)?;
+        let ctype = ChunkType::from_str(str::from_utf8(&type_bytes)
+            .map_err(|e| Box::new(e) as Error)?)?;
+        if !ctype.is_valid() {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidInput, "invalid chunk type")));
+        }
+
+        let mut hasher = Hasher::new();
+        hasher.update(&type_bytes);
+
+        let mut data = Vec::new();
+        (&mut *r).take(length as u64).read_to_end(&mut data)?;
+        if data.len() != length {
+            return Err(Box::new(io::Error::new(io::ErrorKind::UnexpectedEof, "truncated chunk data")));
+        }
+        hasher.update(&data);
+
+        let mut crc_bytes = [0u8; 4];
+        r.read_exact(&mut crc_bytes)?;
+
+        if hasher.finalize() != u32::from_be_bytes(crc_bytes) {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "invalid CRC provided")));
+        }
+
+        Ok(Some(Chunk { chunk_type: ctype, data }))
+    }
  }
 
+/// Reads exactly `buf.len()` bytes, returning `Ok(false)` if the stream was already at EOF,
+/// or an `UnexpectedEof` error if it ran out partway through (a truncated chunk header).
+fn read_exact_or_eof<R: Read>(r: &mut R, buf: &mut [u8]) -> Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match r.read(&mut buf[filled..])? {
+            0 if filled == 0 => return Ok(false),
+            0 => return Err(Box::new(io::Error::new(io::ErrorKind::UnexpectedEof, "truncated chunk header"))),
+            n => filled += n,
+        }
+    }
+    Ok(true)
+}
+
 impl TryFrom<&[u8]> for Chunk {
     type Error = Error;
     fn try_from(value: &[u8]) -> Result<Self> {
@@ -82,6 +324,9 @@ impl TryFrom<&[u8]> for Chunk {
         };
 
         let length_field = u32::from_be_bytes(value[..4].try_into().unwrap()) as usize;
+        if value.len() < 12 + length_field {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidInput, "invalid length")));
+        }
         let data = &value[8..(8 + length_field)];
         let chunk = Chunk{chunk_type: ctype, data: data.to_vec()};
 
@@ -96,7 +341,13 @@ impl TryFrom<&[u8]> for Chunk {
 
 impl std::fmt::Display for Chunk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.data_as_string().unwrap())
+        match self.data_as_string() {
+            Ok(s) => write!(f, "{}", s),
+            Err(_) => match self.structured_payload() {
+                Ok((header, _)) => write!(f, "{:#?}", header),
+                Err(_) => write!(f, "<binary chunk data>"),
+            },
+        }
     }
 }
 
@@ -225,7 +476,130 @@ mod tests {
             .collect();
         
         let chunk: Chunk = TryFrom::try_from(chunk_data.as_ref()).unwrap();
-        
+
         let _chunk_string = format!("{}", chunk);
     }
+
+    #[test]
+    fn test_structured_payload_roundtrip() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let mut header = std::collections::HashMap::new();
+        header.insert(String::from("author"), Value::from("ferris"));
+        header.insert(String::from("index"), Value::from(3));
+
+        let chunk = Chunk::new_structured(chunk_type, header.clone(), b"hidden bytes".to_vec()).unwrap();
+        let (decoded_header, decoded_body) = chunk.structured_payload().unwrap();
+
+        assert_eq!(decoded_header, header);
+        assert_eq!(decoded_body, b"hidden bytes".to_vec());
+    }
+
+    #[test]
+    fn test_structured_payload_display_falls_back_to_header() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let mut header = std::collections::HashMap::new();
+        header.insert(String::from("content-type"), Value::from("application/octet-stream"));
+
+        // Non-UTF-8 body, so `Display` can't print it as a plain string.
+        let chunk = Chunk::new_structured(chunk_type, header, vec![0xff, 0xfe]).unwrap();
+        let rendered = format!("{}", chunk);
+
+        assert!(rendered.contains("content-type"));
+    }
+
+    #[test]
+    fn test_armored_string_roundtrip() {
+        let chunk = testing_chunk();
+        let armored = chunk.to_armored_string();
+
+        assert!(armored.starts_with(ARMOR_HEADER));
+        assert!(armored.ends_with(ARMOR_FOOTER));
+
+        let decoded = Chunk::from_armored_str(&armored).unwrap();
+        assert_eq!(decoded.as_bytes(), chunk.as_bytes());
+    }
+
+    #[test]
+    fn test_from_armored_str_rejects_tampered_body() {
+        let chunk = testing_chunk();
+        let armored = chunk.to_armored_string();
+
+        // The first 'A' is the first Base64 character of the body; changing it corrupts the
+        // decoded length field, so the bytes no longer parse as a valid chunk.
+        let tampered = armored.replacen('A', "B", 1);
+
+        let result = Chunk::from_armored_str(&tampered);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_new_list_items_roundtrip() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let items = vec![
+            b"first secret".to_vec(),
+            b"second secret".to_vec(),
+            vec![],
+            vec![0x41; 80],
+        ];
+
+        let chunk = Chunk::new_list(chunk_type, items.clone());
+        assert_eq!(chunk.items().unwrap(), items);
+    }
+
+    #[test]
+    fn test_items_rejects_truncated_data() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunk = Chunk::new_list(chunk_type, vec![b"a secret".to_vec()]);
+
+        let mut truncated_bytes = chunk.data.clone();
+        truncated_bytes.pop();
+        let truncated = Chunk::new(ChunkType::from_str("RuSt").unwrap(), truncated_bytes);
+
+        assert!(truncated.items().is_err());
+    }
+
+    #[test]
+    fn test_from_reader_valid_chunk() {
+        let chunk = testing_chunk();
+        let bytes = chunk.as_bytes();
+
+        let mut reader = bytes.as_slice();
+        let decoded = Chunk::from_reader(&mut reader).unwrap().unwrap();
+
+        assert_eq!(decoded.as_bytes(), bytes);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_from_reader_clean_eof() {
+        let mut reader: &[u8] = &[];
+        assert!(Chunk::from_reader(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_from_reader_truncated_header() {
+        let mut reader: &[u8] = &[0, 0];
+        assert!(Chunk::from_reader(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_from_reader_rejects_bogus_length() {
+        let chunk = testing_chunk();
+        let mut bytes = chunk.as_bytes();
+        bytes[0..4].copy_from_slice(&u32::MAX.to_be_bytes());
+
+        let mut reader = bytes.as_slice();
+        assert!(Chunk::from_reader(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_from_reader_rejects_bad_crc() {
+        let chunk = testing_chunk();
+        let mut bytes = chunk.as_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xff;
+
+        let mut reader = bytes.as_slice();
+        assert!(Chunk::from_reader(&mut reader).is_err());
+    }
 }
\ No newline at end of file
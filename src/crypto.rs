@@ -0,0 +1,140 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, KeyInit, OsRng, rand_core::RngCore};
+use aes_gcm::{Aes256Gcm, Nonce};
+use hkdf::Hkdf;
+use p256::ecdh::EphemeralSecret;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use p256::{PublicKey, SecretKey};
+use sha2::Sha256;
+
+use crate::{Error, Result};
+
+const NONCE_LEN: usize = 12;
+const EPHEMERAL_PUBKEY_LEN: usize = 33;
+const HKDF_INFO: &[u8] = b"pngme-ecdh-chunk";
+
+/// Marks a chunk type as carrying an ECDH-encrypted payload by forcing its
+/// second byte lowercase, mirroring the PNG "private" (non-public) chunk convention.
+pub fn private_chunk_type(chunk_type: &str) -> String {
+    let mut bytes: Vec<u8> = chunk_type.bytes().collect();
+    if let Some(b) = bytes.get_mut(1) {
+        *b = b.to_ascii_lowercase();
+    }
+    String::from_utf8(bytes).unwrap_or_else(|_| String::from(chunk_type))
+}
+
+pub fn load_public_key(path: &Path) -> Result<PublicKey> {
+    let pem = fs::read_to_string(path)?;
+    PublicKey::from_public_key_pem(&pem)
+        .map_err(|e| Box::new(io::Error::new(io::ErrorKind::InvalidData, e.to_string())) as Error)
+}
+
+pub fn load_private_key(path: &Path) -> Result<SecretKey> {
+    let pem = fs::read_to_string(path)?;
+    SecretKey::from_pkcs8_pem(&pem)
+        .map_err(|e| Box::new(io::Error::new(io::ErrorKind::InvalidData, e.to_string())) as Error)
+}
+
+/// Encrypts `payload` for `recipient` using an ephemeral P-256 keypair: ECDH derives a
+/// shared secret, HKDF-SHA256 stretches it into an AES-256-GCM key, and the result is
+/// packed as `ephemeral_pubkey || nonce || ciphertext (includes the AEAD tag)`.
+pub fn encrypt(recipient: &PublicKey, payload: &[u8]) -> Result<Vec<u8>> {
+    let ephemeral = EphemeralSecret::random(&mut OsRng);
+    let ephemeral_pubkey = ephemeral.public_key();
+    let shared_secret = ephemeral.diffie_hellman(recipient);
+
+    let cipher = Aes256Gcm::new_from_slice(&derive_key(shared_secret.raw_secret_bytes())?)
+        .map_err(|e| Box::new(io::Error::new(io::ErrorKind::InvalidData, e.to_string())) as Error)?;
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from(nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, payload)
+        .map_err(|_| Box::new(io::Error::new(io::ErrorKind::InvalidData, "encryption failed")) as Error)?;
+
+    let mut envelope = ephemeral_pubkey.to_encoded_point(true).as_bytes().to_vec();
+    envelope.extend_from_slice(&nonce_bytes);
+    envelope.extend_from_slice(&ciphertext);
+    Ok(envelope)
+}
+
+/// Reverses [`encrypt`]: re-derives the shared secret from the recipient's private key
+/// and the ephemeral public key stored in the envelope, then opens the AEAD ciphertext.
+pub fn decrypt(recipient_private: &SecretKey, envelope: &[u8]) -> Result<Vec<u8>> {
+    if envelope.len() < EPHEMERAL_PUBKEY_LEN + NONCE_LEN {
+        return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "envelope too short")));
+    }
+
+    let (ephemeral_pubkey_bytes, rest) = envelope.split_at(EPHEMERAL_PUBKEY_LEN);
+    let (nonce_bytes, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let ephemeral_pubkey = PublicKey::from_sec1_bytes(ephemeral_pubkey_bytes)
+        .map_err(|e| Box::new(io::Error::new(io::ErrorKind::InvalidData, e.to_string())) as Error)?;
+
+    let shared_secret = p256::ecdh::diffie_hellman(
+        recipient_private.to_nonzero_scalar(),
+        ephemeral_pubkey.as_affine(),
+    );
+
+    let cipher = Aes256Gcm::new_from_slice(&derive_key(shared_secret.raw_secret_bytes())?)
+        .map_err(|e| Box::new(io::Error::new(io::ErrorKind::InvalidData, e.to_string())) as Error)?;
+    let nonce_bytes: [u8; NONCE_LEN] = nonce_bytes.try_into().unwrap();
+    let nonce = Nonce::from(nonce_bytes);
+
+    cipher
+        .decrypt(&nonce, ciphertext)
+        .map_err(|_| Box::new(io::Error::new(io::ErrorKind::InvalidData, "decryption failed")) as Error)
+}
+
+fn derive_key(shared_secret: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, shared_secret)
+        .expand(HKDF_INFO, &mut key)
+        .map_err(|e| Box::new(io::Error::new(io::ErrorKind::InvalidData, e.to_string())) as Error)?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_private_chunk_type_lowercases_second_byte() {
+        assert_eq!(private_chunk_type("RUSt"), "RuSt");
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let secret = SecretKey::random(&mut OsRng);
+        let public = secret.public_key();
+
+        let envelope = encrypt(&public, b"hidden message").unwrap();
+        let plaintext = decrypt(&secret, &envelope).unwrap();
+
+        assert_eq!(plaintext, b"hidden message");
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampered_ciphertext() {
+        let secret = SecretKey::random(&mut OsRng);
+        let public = secret.public_key();
+
+        let mut envelope = encrypt(&public, b"hidden message").unwrap();
+        let last = envelope.len() - 1;
+        envelope[last] ^= 0xff;
+
+        assert!(decrypt(&secret, &envelope).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_short_envelope() {
+        let secret = SecretKey::random(&mut OsRng);
+        assert!(decrypt(&secret, &[0u8; 4]).is_err());
+    }
+}
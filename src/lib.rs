@@ -0,0 +1,8 @@
+pub mod args;
+pub mod chunk;
+pub mod chunk_type;
+pub mod crypto;
+pub mod png;
+
+pub type Error = Box<dyn std::error::Error>;
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,208 @@
+use std::io::{self, Read, Write};
+
+use crate::chunk::Chunk;
+use crate::{Error, Result};
+
+pub struct Png {
+    header: [u8; 8],
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Png {
+        Png { header: Self::STANDARD_HEADER, chunks }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Png> {
+        if bytes.len() < 8 || bytes[..8] != Self::STANDARD_HEADER {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "invalid PNG signature")));
+        }
+
+        let mut chunks = Vec::new();
+        let mut remainder = &bytes[8..];
+        while !remainder.is_empty() {
+            let chunk = Chunk::try_from(remainder)?;
+            let consumed = 12 + chunk.length() as usize;
+            chunks.push(chunk);
+            remainder = &remainder[consumed..];
+        }
+
+        Ok(Png { header: Self::STANDARD_HEADER, chunks })
+    }
+
+    /// Parses a PNG from `r` one chunk at a time instead of loading the whole file up front.
+    pub fn from_reader<R: Read>(r: &mut R) -> Result<Png> {
+        let mut header = [0u8; 8];
+        r.read_exact(&mut header)?;
+        if header != Self::STANDARD_HEADER {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "invalid PNG signature")));
+        }
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = Chunk::from_reader(r)? {
+            chunks.push(chunk);
+        }
+
+        Ok(Png { header, chunks })
+    }
+
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    pub fn remove_first_chunk(&mut self, chunk_type: &str) -> Result<Chunk> {
+        let index = self.chunks.iter()
+            .position(|c| c.chunk_type().to_string() == chunk_type)
+            .ok_or_else(|| Box::new(io::Error::new(io::ErrorKind::NotFound, "chunk type not present")) as Error)?;
+
+        Ok(self.chunks.remove(index))
+    }
+
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks.iter().find(|c| c.chunk_type().to_string() == chunk_type)
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header.to_vec();
+        for chunk in &self.chunks {
+            bytes.extend(chunk.as_bytes());
+        }
+
+        bytes
+    }
+
+    /// Writes the header and each chunk directly to `w`, one at a time.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<()> {
+        w.write_all(&self.header)?;
+        for chunk in &self.chunks {
+            chunk.write_to(w)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Result<Chunk> {
+        let chunk_type = ChunkType::from_str(chunk_type)?;
+        let data: Vec<u8> = data.bytes().collect();
+
+        Ok(Chunk::new(chunk_type, data))
+    }
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("FrSt", "I am the first chunk").unwrap(),
+            chunk_from_strings("miDl", "I am another chunk").unwrap(),
+            chunk_from_strings("LASt", "I am the last chunk").unwrap(),
+        ]
+    }
+
+    fn testing_png() -> Png {
+        Png::from_chunks(testing_chunks())
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let png = testing_png();
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_from_bytes_valid() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+
+        let decoded = Png::from_bytes(bytes.as_slice()).unwrap();
+        assert_eq!(decoded.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_from_bytes_invalid_header() {
+        let mut bytes = Png::STANDARD_HEADER.to_vec();
+        bytes[0] = 0;
+
+        let result = Png::from_bytes(bytes.as_slice());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_append_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "a new chunk").unwrap());
+
+        assert_eq!(png.chunks().len(), 4);
+        assert_eq!(png.chunk_by_type("TeSt").unwrap().to_string(), "a new chunk");
+    }
+
+    #[test]
+    fn test_remove_first_chunk() {
+        let mut png = testing_png();
+        let removed = png.remove_first_chunk("miDl").unwrap();
+
+        assert_eq!(removed.chunk_type().to_string(), "miDl");
+        assert_eq!(png.chunks().len(), 2);
+        assert!(png.chunk_by_type("miDl").is_none());
+    }
+
+    #[test]
+    fn test_remove_first_chunk_missing() {
+        let mut png = testing_png();
+        let result = png.remove_first_chunk("NoNe");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_chunk_by_type() {
+        let png = testing_png();
+        let chunk = png.chunk_by_type("FrSt").unwrap();
+        assert_eq!(chunk.to_string(), "I am the first chunk");
+    }
+
+    #[test]
+    fn test_from_reader_valid() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+
+        let mut reader = bytes.as_slice();
+        let decoded = Png::from_reader(&mut reader).unwrap();
+
+        assert_eq!(decoded.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_write_to_matches_as_bytes() {
+        let png = testing_png();
+
+        let mut written = Vec::new();
+        png.write_to(&mut written).unwrap();
+
+        assert_eq!(written, png.as_bytes());
+    }
+
+    #[test]
+    fn test_from_reader_invalid_header() {
+        let mut reader: &[u8] = &[0; 8];
+        assert!(Png::from_reader(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_as_bytes_roundtrip() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+
+        let decoded = Png::from_bytes(bytes.as_slice()).unwrap();
+        assert_eq!(decoded.as_bytes(), bytes);
+    }
+}